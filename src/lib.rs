@@ -43,22 +43,62 @@
 //! ```
 //!
 use std::default::Default;
-use std::{cmp, default, sync};
+use std::io::{IsTerminal, Write};
+use std::time::{Duration, Instant};
+use std::{cmp, default, io, sync};
 
 static DEFAULT_WIDTH: usize = 50;
+static MIN_WIDTH: usize = 10;
+static MAX_WIDTH: usize = 200;
 static DEFAULT_TICK: char = '|';
 static DEFAULT_BAR: char = '=';
 static DEFAULT_INDICATOR: char = '#';
+/// Durations shorter than this are treated as zero when computing
+/// throughput, so an instantaneous `finish()` doesn't report an absurd rate
+static MIN_ELAPSED_SECS: f64 = 1e-3;
 static SEGMENTS: [usize; 4] = [10, 5, 4, 2];
 
+/// What a progress count represents
+///
+/// This only affects how the summary lines printed on
+/// [`finish`](ProgressBar::finish) format totals and rates: the
+/// [timing](Style::timing) line and the [`LabelStyle::Ratio`] line.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Unit {
+    /// A plain count of steps or items
+    #[default]
+    Count,
+    /// A number of bytes, formatted with binary prefixes (`KB`, `MB`, ...)
+    Bytes,
+}
+
+/// How the summary on [`finish`](ProgressBar::finish) represents progress
+///
+/// The tick labels drawn above the bar are always percentages: segments are
+/// sized for a 3-char `XX%` label, so a `count/max_progress` label would
+/// overflow it for any but the smallest `max_progress`. `Ratio` therefore
+/// only changes the final summary line.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum LabelStyle {
+    /// Only the `XX%` tick labels above the bar; no extra line on finish
+    #[default]
+    Percentage,
+    /// A `count/max_progress` label on the final summary line
+    Ratio,
+}
+
 /// Progress bar style
 #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct Style {
-    width: usize,
+    width: Option<usize>,
     labels: bool,
     tick: char,
     bar: char,
     indicator: char,
+    force: bool,
+    timing: bool,
+    unit: Unit,
+    label_style: LabelStyle,
 }
 
 impl Style {
@@ -78,6 +118,11 @@ impl Style {
 
     /// Set the progress bar width in characters
     ///
+    /// If this is never called, the width is taken from the `COLUMNS`
+    /// environment variable, clamped to 10 to 200 characters, and is 50
+    /// characters when `COLUMNS` is unset or not a positive number. The
+    /// size of the terminal itself is not queried.
+    ///
     /// # Example
     ///
     /// Create a progress bar with a width of 80 characters:
@@ -87,10 +132,15 @@ impl Style {
     /// let bar = logbar::ProgressBar::with_style(max_progress, style);
     /// ```
     pub fn width(mut self, width: usize) -> Self {
-        self.width = width;
+        self.width = Some(width);
         self
     }
 
+    /// Resolve the effective progress bar width
+    fn resolved_width(&self) -> usize {
+        self.width.unwrap_or_else(detect_width)
+    }
+
     /// Toggle progress bar labels of the form XX%
     ///
     /// # Example
@@ -153,6 +203,82 @@ impl Style {
         self.indicator = indicator;
         self
     }
+
+    /// Force the progress bar to be drawn
+    ///
+    /// By default, the progress bar disables itself when it detects that
+    /// it is not writing to an interactive terminal, that `TERM=dumb`, or
+    /// that the `CI` environment variable is set, since in those cases the
+    /// output is usually redirected into a log file. Call this method with
+    /// `true` to draw the bar regardless of the environment.
+    ///
+    /// # Example
+    ///
+    /// Create a progress bar that is drawn even when piped to a log file:
+    /// ```rust
+    /// let style = logbar::Style::new().force(true);
+    /// let max_progress = 100;
+    /// let bar = logbar::ProgressBar::with_style(max_progress, style);
+    /// ```
+    pub fn force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Append an elapsed time and throughput summary line on finish
+    ///
+    /// # Example
+    ///
+    /// Create a progress bar that prints a summary once it finishes:
+    /// ```rust
+    /// let style = logbar::Style::new().timing(true);
+    /// let max_progress = 100;
+    /// let bar = logbar::ProgressBar::with_style(max_progress, style);
+    /// bar.finish();
+    /// ```
+    pub fn timing(mut self, timing: bool) -> Self {
+        self.timing = timing;
+        self
+    }
+
+    /// Set what a progress count represents
+    ///
+    /// This is useful for e.g. download or copy loops where [`inc`](ProgressBar::inc)
+    /// receives byte counts rather than step counts: with [`Unit::Bytes`],
+    /// the [timing](Style::timing) summary formats totals and rates with
+    /// binary prefixes (`KB`, `MB`, ...) instead of a raw item count.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let style = logbar::Style::new().unit(logbar::Unit::Bytes).timing(true);
+    /// let max_progress = 1024 * 1024;
+    /// let bar = logbar::ProgressBar::with_style(max_progress, style);
+    /// bar.finish();
+    /// ```
+    pub fn unit(mut self, unit: Unit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Choose how progress is labelled
+    ///
+    /// See [`LabelStyle`] for how this affects the final summary line.
+    ///
+    /// # Example
+    ///
+    /// Create a progress bar that adds a `count/max_progress` line to its
+    /// final summary, next to the usual percentage tick labels:
+    /// ```rust
+    /// let style = logbar::Style::new().label_style(logbar::LabelStyle::Ratio);
+    /// let max_progress = 100;
+    /// let bar = logbar::ProgressBar::with_style(max_progress, style);
+    /// bar.finish();
+    /// ```
+    pub fn label_style(mut self, label_style: LabelStyle) -> Self {
+        self.label_style = label_style;
+        self
+    }
 }
 
 impl default::Default for Style {
@@ -168,15 +294,82 @@ impl default::Default for Style {
     /// ```
     fn default() -> Self {
         Style {
-            width: DEFAULT_WIDTH,
+            width: None,
             labels: true,
             tick: DEFAULT_TICK,
             bar: DEFAULT_BAR,
             indicator: DEFAULT_INDICATOR,
+            force: false,
+            timing: false,
+            unit: Unit::Count,
+            label_style: LabelStyle::Percentage,
         }
     }
 }
 
+/// Format a duration as `HhMmSs`, dropping leading zero units, or as
+/// `S.Ds` when it is shorter than a minute
+fn fmt_duration(d: Duration) -> String {
+    let total_secs = d.as_secs();
+    let h = total_secs / 3600;
+    let m = (total_secs % 3600) / 60;
+    let s = total_secs % 60;
+    if h > 0 {
+        format!("{h}h{m}m{s}s")
+    } else if m > 0 {
+        format!("{m}m{s}s")
+    } else {
+        format!("{:.1}s", d.as_secs_f64())
+    }
+}
+
+static KB: f64 = 1024.0;
+static MB: f64 = 1024.0 * 1024.0;
+static GB: f64 = 1024.0 * 1024.0 * 1024.0;
+static TB: f64 = 1024.0 * 1024.0 * 1024.0 * 1024.0;
+
+/// Format a byte count with binary prefixes (`KB`, `MB`, `GB`, `TB`),
+/// falling back to raw bytes below 1024
+fn fmt_bytes(n: f64) -> String {
+    if n >= TB {
+        format!("{:.2}TB", n / TB)
+    } else if n >= GB {
+        format!("{:.2}GB", n / GB)
+    } else if n >= MB {
+        format!("{:.2}MB", n / MB)
+    } else if n >= KB {
+        format!("{:.2}KB", n / KB)
+    } else {
+        format!("{n:.0}B")
+    }
+}
+
+/// Detect the terminal width, falling back to `DEFAULT_WIDTH` if it cannot
+/// be determined
+///
+/// This crate only depends on the standard library, which has no portable
+/// way to query the tty size directly, so only the `COLUMNS` environment
+/// variable is honored; many shells only set it for interactive use and do
+/// not export it to child processes, so this is best treated as a clamp on
+/// an explicitly-exported width rather than a reliable auto-size. Either
+/// way, the result is clamped to `[MIN_WIDTH, MAX_WIDTH]`.
+fn detect_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.parse::<usize>().ok())
+        .filter(|w| *w > 0)
+        .map(|w| w.clamp(MIN_WIDTH, MAX_WIDTH))
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+/// Whether the environment asks us to stay quiet, e.g. because `TERM=dumb`
+/// or the `CI` environment variable is set
+fn is_noninteractive_env() -> bool {
+    let dumb_term = std::env::var_os("TERM").is_some_and(|t| t == "dumb");
+    let ci = std::env::var_os("CI").is_some();
+    dumb_term || ci
+}
+
 #[derive(Clone, Default, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 struct Counter {
     count: usize,
@@ -184,12 +377,30 @@ struct Counter {
     finished: bool,
 }
 
+type Sink = sync::Arc<sync::Mutex<Box<dyn io::Write + Send>>>;
+
 /// A log-friendly progress bar
-#[derive(Debug)]
 pub struct ProgressBar {
     counter: sync::Arc<sync::Mutex<Counter>>,
     max_progress: usize,
     style: Style,
+    sink: Sink,
+    width: usize,
+    enabled: bool,
+    start: Instant,
+}
+
+impl std::fmt::Debug for ProgressBar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProgressBar")
+            .field("counter", &self.counter)
+            .field("max_progress", &self.max_progress)
+            .field("style", &self.style)
+            .field("width", &self.width)
+            .field("enabled", &self.enabled)
+            .field("start", &self.start)
+            .finish_non_exhaustive()
+    }
 }
 
 fn num_segments(width: usize) -> usize {
@@ -203,42 +414,50 @@ fn num_segments(width: usize) -> usize {
     1
 }
 
-fn draw_labels(width: usize, segments: usize) {
+fn segment_label(p: usize, segments: usize) -> String {
+    format!("{}%", p * 100 / segments)
+}
+
+// The tick labels above the bar are always percentages, regardless of
+// `Style::label_style`: segments are sized for a 3-char `XX%` label, and a
+// `count/max_progress` label would overflow that for most `max_progress`
+// values. See `LabelStyle::Ratio`.
+fn draw_labels(w: &mut dyn io::Write, width: usize, segments: usize) {
     debug_assert_eq!(width % segments, 0);
-    eprint!("0% ");
+    let _ = write!(w, "{} ", segment_label(0, segments));
     let seg_width = width / segments;
     for p in 1..=segments {
         for _ in 0..(seg_width - 3) {
-            eprint!(" ");
+            let _ = write!(w, " ");
         }
-        eprint!("{}%", p * 100 / segments)
+        let _ = write!(w, "{}", segment_label(p, segments));
     }
-    eprintln!()
+    let _ = writeln!(w);
 }
 
-fn draw_tickbar(style: &Style, segments: usize) {
-    let width = style.width;
+fn draw_tickbar(w: &mut dyn io::Write, style: &Style, width: usize, segments: usize) {
     debug_assert_eq!(width % segments, 0);
-    eprint!("{}", style.tick);
+    let _ = write!(w, "{}", style.tick);
     let seg_width = width / segments;
     for _ in 1..=segments {
         for _ in 0..(seg_width - 1) {
-            eprint!("{}", style.bar);
+            let _ = write!(w, "{}", style.bar);
         }
-        eprint!("{}", style.tick)
+        let _ = write!(w, "{}", style.tick);
     }
-    eprintln!()
+    let _ = writeln!(w);
 }
 
-fn draw_bar(style: &Style) {
-    let width = style.width;
+fn draw_bar(w: &mut dyn io::Write, style: &Style, width: usize) {
     let segments = num_segments(width);
     if width > 3 && style.labels {
-        draw_labels(width, segments)
+        draw_labels(w, width, segments)
     }
     match width.cmp(&1) {
-        cmp::Ordering::Greater => draw_tickbar(style, segments),
-        cmp::Ordering::Equal => eprintln!("{}", style.tick),
+        cmp::Ordering::Greater => draw_tickbar(w, style, width, segments),
+        cmp::Ordering::Equal => {
+            let _ = writeln!(w, "{}", style.tick);
+        }
         cmp::Ordering::Less => {}
     }
 }
@@ -258,6 +477,11 @@ impl ProgressBar {
 
     /// Create a new progress bar with custom style
     ///
+    /// Unless [`Style::force`] is set, the bar draws nothing when stderr is
+    /// not an interactive terminal, when `TERM=dumb`, or when the `CI`
+    /// environment variable is set, since the output is then most likely
+    /// being redirected into a log file.
+    ///
     /// # Example
     ///
     /// Create a progress bar with a width of 80 characters:
@@ -267,12 +491,57 @@ impl ProgressBar {
     /// let bar = logbar::ProgressBar::with_style(max_progress, style);
     /// ```
     pub fn with_style(max_progress: usize, style: Style) -> Self {
+        let is_terminal = io::stderr().is_terminal();
+        ProgressBar::new_impl(max_progress, style, Box::new(io::stderr()), is_terminal)
+    }
+
+    /// Create a new progress bar with custom style, writing to the given sink
+    ///
+    /// This is useful whenever the progress bar should not be printed to
+    /// stderr, for example to redirect it into a dedicated log file or to
+    /// capture it in a buffer for testing. The given writer is never treated
+    /// as an interactive terminal, so drawing still has to be requested with
+    /// [`Style::force`] unless the caller wants the bar to stay silent.
+    ///
+    /// # Example
+    ///
+    /// Create a progress bar that writes into an in-memory buffer:
+    /// ```rust
+    /// let max_progress = 100;
+    /// let style = logbar::Style::new().force(true);
+    /// let buf = Vec::new();
+    /// let bar = logbar::ProgressBar::with_writer(max_progress, style, buf);
+    /// bar.finish();
+    /// ```
+    pub fn with_writer<W: io::Write + Send + 'static>(
+        max_progress: usize,
+        style: Style,
+        writer: W,
+    ) -> Self {
+        ProgressBar::new_impl(max_progress, style, Box::new(writer), false)
+    }
+
+    fn new_impl(
+        max_progress: usize,
+        style: Style,
+        writer: Box<dyn io::Write + Send>,
+        is_terminal: bool,
+    ) -> Self {
         let counter = sync::Arc::new(sync::Mutex::new(Counter::default()));
-        draw_bar(&style);
+        let sink: Sink = sync::Arc::new(sync::Mutex::new(writer));
+        let width = style.resolved_width();
+        let enabled = style.force || (is_terminal && !is_noninteractive_env());
+        if enabled {
+            draw_bar(&mut *sink.lock().unwrap(), &style, width);
+        }
         ProgressBar {
             counter,
             max_progress,
             style,
+            sink,
+            width,
+            enabled,
+            start: Instant::now(),
         }
     }
 
@@ -312,7 +581,7 @@ impl ProgressBar {
             let mut c = self.counter.lock().unwrap();
             let new_count = cmp::min(c.count + i, self.max_progress);
             let new_progress = if self.max_progress > 0 {
-                new_count * self.style.width / self.max_progress
+                new_count * self.width / self.max_progress
             } else {
                 0
             };
@@ -324,15 +593,78 @@ impl ProgressBar {
             };
             diff
         };
+        if !self.enabled {
+            return;
+        }
+        let mut sink = self.sink.lock().unwrap();
         for _ in 0..new_progress {
-            eprint!("{}", self.style.indicator);
+            let _ = write!(sink, "{}", self.style.indicator);
         }
     }
 
+    /// Time elapsed since the progress bar was created
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let max_progress = 50;
+    /// let bar = logbar::ProgressBar::new(max_progress);
+    /// assert!(bar.elapsed().as_secs() < 1);
+    /// ```
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Average number of increments per second since the progress bar was
+    /// created
+    ///
+    /// Returns `0.0` while the elapsed time is still effectively zero, to
+    /// avoid reporting an absurd rate for an instantaneous measurement.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let max_progress = 50;
+    /// let bar = logbar::ProgressBar::new(max_progress);
+    /// let rate = bar.per_sec();
+    /// ```
+    pub fn per_sec(&self) -> f64 {
+        let count = self.counter.lock().unwrap().count;
+        let secs = self.elapsed().as_secs_f64();
+        if secs < MIN_ELAPSED_SECS {
+            0.0
+        } else {
+            count as f64 / secs
+        }
+    }
+
+    /// Estimated time until the progress bar finishes
+    ///
+    /// Returns [`Duration::ZERO`] once the bar is finished or while the
+    /// throughput is still unknown.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let max_progress = 50;
+    /// let bar = logbar::ProgressBar::new(max_progress);
+    /// let eta = bar.eta();
+    /// ```
+    pub fn eta(&self) -> Duration {
+        let count = self.counter.lock().unwrap().count;
+        let rate = self.per_sec();
+        if rate == 0.0 || count >= self.max_progress {
+            return Duration::ZERO;
+        }
+        let remaining = (self.max_progress - count) as f64;
+        Duration::from_secs_f64(remaining / rate)
+    }
+
     /// Finish the progress bar
     ///
     /// This method sets the progress to 100% and moves to the next line
-    /// after the progress bar
+    /// after the progress bar. If [`Style::timing`] is enabled, a summary
+    /// line with the elapsed time and throughput is appended.
     ///
     /// # Example
     ///
@@ -346,20 +678,223 @@ impl ProgressBar {
         self.inc(self.max_progress);
         let mut c = self.counter.lock().unwrap();
         if !c.finished {
-            eprintln!();
+            if self.enabled {
+                let mut sink = self.sink.lock().unwrap();
+                let _ = writeln!(sink);
+                if self.style.label_style == LabelStyle::Ratio {
+                    let ratio = match self.style.unit {
+                        Unit::Count => format!("{}/{}", c.count, self.max_progress),
+                        Unit::Bytes => format!(
+                            "{}/{}",
+                            fmt_bytes(c.count as f64),
+                            fmt_bytes(self.max_progress as f64)
+                        ),
+                    };
+                    let _ = writeln!(sink, "{ratio}");
+                }
+                if self.style.timing {
+                    let elapsed = self.start.elapsed();
+                    let rate = if elapsed.as_secs_f64() < MIN_ELAPSED_SECS {
+                        0.0
+                    } else {
+                        c.count as f64 / elapsed.as_secs_f64()
+                    };
+                    let (total, rate) = match self.style.unit {
+                        Unit::Count => (format!("{} items", c.count), format!("{rate:.0}/s")),
+                        Unit::Bytes => {
+                            (fmt_bytes(c.count as f64), format!("{}/s", fmt_bytes(rate)))
+                        }
+                    };
+                    let _ = writeln!(
+                        sink,
+                        "done: {total} in {} ({rate})",
+                        fmt_duration(elapsed)
+                    );
+                }
+            }
             c.finished = true;
         }
     }
 }
 
+/// An iterator wrapped by [`ProgressIterator`]
+///
+/// Drives a [`ProgressBar`] forward on every call to `next`, and finishes
+/// it once the wrapped iterator is exhausted or the wrapper is dropped.
+#[derive(Debug)]
+pub struct ProgressBarIter<I> {
+    it: I,
+    bar: ProgressBar,
+}
+
+impl<I: Iterator> Iterator for ProgressBarIter<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.it.next();
+        if next.is_some() {
+            self.bar.inc(1);
+        } else {
+            self.bar.finish();
+        }
+        next
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+impl<I> Drop for ProgressBarIter<I> {
+    fn drop(&mut self) {
+        self.bar.finish();
+    }
+}
+
+/// Extension trait adding a progress bar to iterators with a known length
+///
+/// # Example
+///
+/// ```rust
+/// use logbar::ProgressIterator;
+///
+/// for _ in (0..10).progress() {
+///     // do some work
+/// }
+/// ```
+pub trait ProgressIterator: Sized + ExactSizeIterator {
+    /// Wrap this iterator in a [`ProgressBar`] with the default style
+    fn progress(self) -> ProgressBarIter<Self> {
+        self.progress_with_style(Style::default())
+    }
+
+    /// Wrap this iterator in a [`ProgressBar`] with a custom style
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use logbar::{ProgressIterator, Style};
+    ///
+    /// let style = Style::new().labels(false);
+    /// for _ in (0..10).progress_with_style(style) {
+    ///     // do some work
+    /// }
+    /// ```
+    fn progress_with_style(self, style: Style) -> ProgressBarIter<Self> {
+        let bar = ProgressBar::with_style(self.len(), style);
+        ProgressBarIter { it: self, bar }
+    }
+}
+
+impl<I: ExactSizeIterator> ProgressIterator for I {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    //TODO: capture stderr and check
-    // for the time being, run
-    // cargo test -- --nocapture --test-threads=1
-    // and check the output manually
+    #[derive(Clone, Default)]
+    struct SharedBuf(sync::Arc<sync::Mutex<Vec<u8>>>);
+
+    impl io::Write for SharedBuf {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn captured_output() {
+        let max_progress = 10;
+        let buf = SharedBuf::default();
+        let style = Style::new().force(true);
+        let bar = ProgressBar::with_writer(max_progress, style, buf.clone());
+        bar.inc(max_progress);
+        bar.finish();
+
+        let out = buf.0.lock().unwrap().clone();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("0%"));
+        assert!(out.contains("100%"));
+        assert_eq!(
+            out.matches(DEFAULT_INDICATOR).count(),
+            DEFAULT_WIDTH
+        );
+    }
+
+    #[test]
+    fn timing_summary() {
+        let max_progress = 5;
+        let buf = SharedBuf::default();
+        let style = Style::new().force(true).timing(true);
+        let bar = ProgressBar::with_writer(max_progress, style, buf.clone());
+        bar.finish();
+
+        let out = buf.0.lock().unwrap().clone();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("done: 5 items in"));
+    }
+
+    #[test]
+    fn byte_unit_summary() {
+        let max_progress = 2 * 1024 * 1024;
+        let buf = SharedBuf::default();
+        let style = Style::new().force(true).timing(true).unit(Unit::Bytes);
+        let bar = ProgressBar::with_writer(max_progress, style, buf.clone());
+        bar.finish();
+
+        let out = buf.0.lock().unwrap().clone();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("done: 2.00MB in"));
+    }
+
+    #[test]
+    fn ratio_labels() {
+        let max_progress = 200;
+        let buf = SharedBuf::default();
+        let style = Style::new().force(true).label_style(LabelStyle::Ratio);
+        let bar = ProgressBar::with_writer(max_progress, style, buf.clone());
+        bar.finish();
+
+        let out = buf.0.lock().unwrap().clone();
+        let out = String::from_utf8(out).unwrap();
+        // the tick labels above the bar stay percentages; only the final
+        // summary line uses the ratio
+        assert!(out.contains("0%"));
+        assert!(out.contains("100%"));
+        assert!(out.contains("200/200"));
+    }
+
+    #[test]
+    fn ratio_labels_bytes() {
+        let max_progress = 2 * 1024 * 1024;
+        let buf = SharedBuf::default();
+        let style = Style::new()
+            .force(true)
+            .label_style(LabelStyle::Ratio)
+            .unit(Unit::Bytes);
+        let bar = ProgressBar::with_writer(max_progress, style, buf.clone());
+        bar.finish();
+
+        let out = buf.0.lock().unwrap().clone();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("2.00MB/2.00MB"));
+    }
+
+    #[test]
+    fn progress_iterator() {
+        let style = Style::new().force(true);
+        let items: Vec<_> = (0..5).progress_with_style(style).collect();
+        assert_eq!(items, vec![0, 1, 2, 3, 4]);
+
+        // early termination still finishes the bar via `Drop`
+        let style = Style::new().force(true);
+        let mut it = (0..5).progress_with_style(style);
+        it.next();
+        drop(it);
+    }
 
     #[test]
     fn construct() {
@@ -367,15 +902,15 @@ mod tests {
         let max_progress = 1000;
         {
             let bar = ProgressBar::new(max_progress);
-            assert_eq!(bar.style().width, DEFAULT_WIDTH);
+            assert_eq!(bar.style().width, None);
         }
 
         {
             let width = 80;
             let mut style = Style::default();
-            style.width = width;
+            style.width = Some(width);
             let bar = ProgressBar::with_style(max_progress, style);
-            assert_eq!(bar.style().width, width);
+            assert_eq!(bar.style().width, Some(width));
         }
     }
 